@@ -0,0 +1,307 @@
+use super::sram23x::NvSram;
+use super::*;
+use hal::blocking::spi::{Transfer, Write};
+use hal::digital::v2::OutputPin;
+
+/// Maximum length, in bytes, of a `ConfigStore` key
+pub const MAX_KEY_LEN: usize = 16;
+
+/// Sentinel `value_len` marking a tombstone (removed) record
+const TOMBSTONE: u16 = 0xFFFF;
+
+/// Errors specific to `ConfigStore`, in addition to the usual `Error<S, P>`
+#[derive(Debug)]
+pub enum ConfigError<S, P> {
+    /// Error from the underlying `Sram23x` device
+    Sram(Error<S, P>),
+    /// Key is longer than `MAX_KEY_LEN`
+    KeyTooLong,
+    /// No room left for a new index entry; `N` needs to be larger
+    IndexFull,
+    /// No room left in the device for another record; call `compact()` or `erase()`
+    StorageFull,
+    /// Caller-supplied buffer is too small to hold the stored value
+    BufferTooSmall,
+    /// Value is `>= 0xFFFF` bytes long, so its length can't be distinguished from `TOMBSTONE`
+    ValueTooLong,
+}
+
+impl<S, P> From<Error<S, P>> for ConfigError<S, P> {
+    fn from(e: Error<S, P>) -> Self {
+        ConfigError::Sram(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    /// Address of the `key_len` byte that starts the record
+    record_start: u32,
+    key_len: u8,
+    key: [u8; MAX_KEY_LEN],
+    /// Address of the record's `value_len` field
+    value_offset: u32,
+    value_len: u16,
+}
+
+/// A journaled key/value config store backed by a battery-backed NVSRAM `Sram23x` device
+///
+/// Records are laid out sequentially from address 0 as
+/// `[key_len:u8][key bytes][value_len:u16][value bytes]`. `set()` appends a new record for
+/// a key, logically superseding any earlier one; `remove()` appends a tombstone record
+/// (`value_len == 0xFFFF`). The latest record for a key wins, so `open()` scans the whole
+/// array once to build an in-RAM index of the latest offset per key. `N` bounds the number
+/// of distinct live keys the index can track.
+pub struct ConfigStore<SPI, CS, HOLD, DT, MIO, const N: usize>
+where
+    DT: NvSram,
+{
+    sram: Sram23x<SPI, CS, HOLD, DT, MIO>,
+    index: [Option<IndexEntry>; N],
+    /// Address one past the last written record, where the next record will be appended
+    next: u32,
+}
+
+impl<SPI, S, P, CS, HOLD, DT, MIO, const N: usize> ConfigStore<SPI, CS, HOLD, DT, MIO, N>
+where
+    SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
+    CS: OutputPin<Error = P>,
+    HOLD: OutputPin<Error = P>,
+    DT: NvSram,
+    MIO: MultiIoSpi<S>,
+{
+    /// Open the config store, scanning the whole array once to build the in-RAM index of
+    /// the latest offset for each live key
+    pub fn open(sram: Sram23x<SPI, CS, HOLD, DT, MIO>) -> Result<Self, ConfigError<S, P>> {
+        let mut store = ConfigStore {
+            sram,
+            index: [None; N],
+            next: 0,
+        };
+        store.scan()?;
+        Ok(store)
+    }
+
+    fn scan(&mut self) -> Result<(), ConfigError<S, P>> {
+        self.index = [None; N];
+        let mut addr = 0u32;
+        while addr <= DT::MAX {
+            let mut key_len_buf = [0u8; 1];
+            self.sram.read(addr, &mut key_len_buf)?;
+            let key_len = key_len_buf[0];
+            // An unwritten record slot reads back as 0x00 or 0xFF; either marks the end of
+            // the journal.
+            if key_len == 0 || key_len == 0xFF || key_len as usize > MAX_KEY_LEN {
+                break;
+            }
+
+            let mut key = [0u8; MAX_KEY_LEN];
+            let value_offset = addr + 1 + key_len as u32;
+            self.sram.read(addr + 1, &mut key[..key_len as usize])?;
+            let mut value_len_buf = [0u8; 2];
+            self.sram.read(value_offset, &mut value_len_buf)?;
+            let value_len = u16::from_be_bytes(value_len_buf);
+            let stored_len = if value_len == TOMBSTONE { 0 } else { value_len };
+            let record_end = value_offset + 2 + stored_len as u32;
+
+            let entry = IndexEntry {
+                record_start: addr,
+                key_len,
+                key,
+                value_offset,
+                value_len,
+            };
+            self.index_upsert(entry)?;
+            addr = record_end;
+        }
+        self.next = addr;
+        Ok(())
+    }
+
+    /// Insert or replace the index slot tracking `entry`'s key
+    fn index_upsert(&mut self, entry: IndexEntry) -> Result<(), ConfigError<S, P>> {
+        if let Some(slot) = self
+            .index
+            .iter_mut()
+            .find(|e| matches!(e, Some(e) if Self::same_key(e, &entry)))
+        {
+            // Mirror `remove()`'s convention: a removed key's slot is `None`, not a stored
+            // tombstone record, so `compact()` never has to special-case tombstones itself.
+            *slot = if entry.value_len == TOMBSTONE { None } else { Some(entry) };
+            return Ok(());
+        }
+        if entry.value_len == TOMBSTONE {
+            // Tombstone for a key we never indexed (e.g. it fell out of a full index); ignore.
+            return Ok(());
+        }
+        match self.index.iter_mut().find(|e| e.is_none()) {
+            Some(slot) => {
+                *slot = Some(entry);
+                Ok(())
+            }
+            None => Err(ConfigError::IndexFull),
+        }
+    }
+
+    fn same_key(entry: &IndexEntry, other: &IndexEntry) -> bool {
+        entry.key_len == other.key_len && entry.key[..entry.key_len as usize] == other.key[..other.key_len as usize]
+    }
+
+    fn find(&self, key: &[u8]) -> Option<&IndexEntry> {
+        self.index.iter().flatten().find(|e| {
+            e.value_len != TOMBSTONE && e.key_len as usize == key.len() && &e.key[..key.len()] == key
+        })
+    }
+
+    /// Look up `key`, copying its value into `buf` if present
+    ///
+    /// Returns `Ok(Some(len))` with the value's length on a hit, `Ok(None)` if the key has no
+    /// live record, or `Err(ConfigError::BufferTooSmall)` if `buf` is shorter than the stored
+    /// value.
+    ///
+    /// Returns an owned length rather than `Option<&[u8]>` because the value lives on the
+    /// `Sram23x` device, not in RAM; a `no_std` driver has nowhere to borrow the bytes from
+    /// without an allocator, so the caller supplies the buffer to copy into instead.
+    pub fn get(&mut self, key: &[u8], buf: &mut [u8]) -> Result<Option<usize>, ConfigError<S, P>> {
+        let entry = match self.find(key) {
+            Some(e) => *e,
+            None => return Ok(None),
+        };
+        let len = entry.value_len as usize;
+        if buf.len() < len {
+            return Err(ConfigError::BufferTooSmall);
+        }
+        self.sram.read(entry.value_offset + 2, &mut buf[..len])?;
+        Ok(Some(len))
+    }
+
+    /// Append a new record for `key`/`value`, superseding any earlier record for the same key
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), ConfigError<S, P>> {
+        if key.len() > MAX_KEY_LEN {
+            return Err(ConfigError::KeyTooLong);
+        }
+        if value.len() >= TOMBSTONE as usize {
+            // A `value_len` of exactly `TOMBSTONE` would make this record indistinguishable
+            // from a removed key on the next `scan()`.
+            return Err(ConfigError::ValueTooLong);
+        }
+        let record_start = self.next;
+        let value_offset = record_start + 1 + key.len() as u32;
+        let record_end = value_offset + 2 + value.len() as u32;
+        if record_end - 1 > DT::MAX {
+            return Err(ConfigError::StorageFull);
+        }
+
+        self.sram.write(record_start, &[key.len() as u8])?;
+        self.sram.write(record_start + 1, key)?;
+        self.sram
+            .write(value_offset, &(value.len() as u16).to_be_bytes())?;
+        self.sram.write(value_offset + 2, value)?;
+
+        let mut key_buf = [0u8; MAX_KEY_LEN];
+        key_buf[..key.len()].clone_from_slice(key);
+        self.index_upsert(IndexEntry {
+            record_start,
+            key_len: key.len() as u8,
+            key: key_buf,
+            value_offset,
+            value_len: value.len() as u16,
+        })?;
+        self.next = record_end;
+        Ok(())
+    }
+
+    /// Append a tombstone record for `key`, so it no longer appears in `get()`/scans
+    pub fn remove(&mut self, key: &[u8]) -> Result<(), ConfigError<S, P>> {
+        if key.len() > MAX_KEY_LEN {
+            return Err(ConfigError::KeyTooLong);
+        }
+        if self.find(key).is_none() {
+            return Ok(());
+        }
+        let record_start = self.next;
+        let value_offset = record_start + 1 + key.len() as u32;
+        let record_end = value_offset + 2;
+        if record_end - 1 > DT::MAX {
+            return Err(ConfigError::StorageFull);
+        }
+
+        self.sram.write(record_start, &[key.len() as u8])?;
+        self.sram.write(record_start + 1, key)?;
+        self.sram.write(value_offset, &TOMBSTONE.to_be_bytes())?;
+
+        if let Some(slot) = self
+            .index
+            .iter_mut()
+            .find(|e| matches!(e, Some(e) if e.key_len as usize == key.len() && &e.key[..key.len()] == key))
+        {
+            *slot = None;
+        }
+        self.next = record_end;
+        Ok(())
+    }
+
+    /// Zero the whole array and reset the store to empty
+    pub fn erase(&mut self) -> Result<(), ConfigError<S, P>> {
+        let zeros = [0u8; 32];
+        let mut addr = 0u32;
+        while addr <= DT::MAX {
+            let len = core::cmp::min(32, DT::MAX - addr + 1) as usize;
+            self.sram.write(addr, &zeros[..len])?;
+            addr += len as u32;
+        }
+        self.index = [None; N];
+        self.next = 0;
+        Ok(())
+    }
+
+    /// Rewrite only the live records, from address 0, to reclaim space taken up by
+    /// superseded and tombstoned records
+    pub fn compact(&mut self) -> Result<(), ConfigError<S, P>> {
+        let old_next = self.next;
+        let mut live: [Option<IndexEntry>; N] = self.index;
+        live[..].sort_unstable_by_key(|e| e.map(|e| e.record_start).unwrap_or(u32::MAX));
+
+        let mut write_cursor = 0u32;
+        let mut buf = [0u8; 32];
+        // `index_upsert` never leaves a tombstone (`value_len == TOMBSTONE`) in the index, but
+        // filter defensively anyway: rewriting one here would copy `value_len` (0xFFFF) bytes
+        // of "value" past the end of the device.
+        for entry in live.iter().flatten().filter(|e| e.value_len != TOMBSTONE) {
+            let new_record_start = write_cursor;
+            let new_value_offset = new_record_start + 1 + entry.key_len as u32;
+
+            self.sram.write(new_record_start, &[entry.key_len])?;
+            self.sram
+                .write(new_record_start + 1, &entry.key[..entry.key_len as usize])?;
+            self.sram
+                .write(new_value_offset, &entry.value_len.to_be_bytes())?;
+
+            let mut remaining = entry.value_len as u32;
+            let mut src = entry.value_offset + 2;
+            let mut dst = new_value_offset + 2;
+            while remaining > 0 {
+                let chunk = core::cmp::min(remaining, buf.len() as u32) as usize;
+                self.sram.read(src, &mut buf[..chunk])?;
+                self.sram.write(dst, &buf[..chunk])?;
+                src += chunk as u32;
+                dst += chunk as u32;
+                remaining -= chunk as u32;
+            }
+
+            write_cursor = new_value_offset + 2 + entry.value_len as u32;
+        }
+
+        // Zero the gap between the new, shorter journal and its old end so a stale byte
+        // there isn't mistaken for the start of another record on the next scan().
+        let mut addr = write_cursor;
+        let zeros = [0u8; 32];
+        while addr < old_next {
+            let len = core::cmp::min(32, old_next - addr) as usize;
+            self.sram.write(addr, &zeros[..len])?;
+            addr += len as u32;
+        }
+
+        self.next = write_cursor;
+        self.scan()
+    }
+}
@@ -12,8 +12,13 @@ This driver allows you to:
 - Write a single byte to a memory address. See: `write_byte()`.
 - Write a 32-byte page starting on a memory address. See: `write_page()`.
 - Write an N-byte array starting on a memory address. See: `write_sequential()`.
+- Read or write an arbitrary number of bytes, auto-switching into sequential mode as needed. See: `read()`, `write()`.
+- Stream a large read/write across many pages in one chip-select transaction, without per-call framing overhead. See: `read_stream()`, `write_stream()`.
 - Enable and disable transmission by managing the _HOLD_ pin.
 - Get/Set the operating mode/status register.
+- Enter 2x/4x throughput Dual/Quad I/O mode on parts that support it. See: `enter_dual_io()`, `enter_quad_io()`, `reset_io()`.
+- With the `embedded-storage` feature, use the chip as an [`embedded-storage`](https://github.com/rust-embedded-community/embedded-storage) `ReadStorage`/`Storage` device.
+- With the `config` feature, use a `M23xv512`/`M23xv1024` NVSRAM chip as a journaled key/value config store. See: `config::ConfigStore`.
 
 Read the [API Documentation](https://docs.rs/sram23x) for more information.
 
@@ -102,12 +107,18 @@ fn main() {
 
 extern crate bit_field;
 extern crate embedded_hal as hal;
+#[cfg(feature = "embedded-storage")]
+extern crate embedded_storage;
 
 mod sram23x;
+#[cfg(feature = "embedded-storage")]
+mod storage;
+#[cfg(feature = "config")]
+pub mod config;
 
 /// Microchip SRAM 23x driver
 #[derive(Debug, Default)]
-pub struct Sram23x<SPI, CS, HOLD, DT> {
+pub struct Sram23x<SPI, CS, HOLD, DT, MIO = NoMultiIo> {
     /// The concrete SPI device implementation
     spi: SPI,
     /// The SPI chip select pin
@@ -118,6 +129,61 @@ pub struct Sram23x<SPI, CS, HOLD, DT> {
     dt: DT,
     /// The operating mode of the device
     pub mode: u8,
+    /// The active SPI bus width (standard single-lane, or 2x/4x Dual/Quad I/O)
+    pub io_mode: IoMode,
+    /// The multi-lane SPI transport used once Dual or Quad I/O has been entered
+    mio: Option<MIO>,
+}
+
+/// The active SPI bus width used for command/address/data transfers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IoMode {
+    /// Standard single-lane SPI
+    #[default]
+    Single,
+    /// Dual I/O (SDI) mode entered via `enter_dual_io()`; data clocked over 2 lines
+    Dual,
+    /// Quad I/O (SQI) mode entered via `enter_quad_io()`; data clocked over 4 lines.
+    /// HOLD is repurposed as SIO3 while in this mode.
+    Quad,
+}
+
+/// A multi-lane SPI transport supplied by the user to drive Dual/Quad I/O transfers
+///
+/// `embedded-hal`'s `Transfer`/`Write` traits assume a single-lane (MOSI/MISO) bus, so
+/// parts that support 2x/4x throughput SDI/SQI modes need this small additional trait,
+/// whose methods are called only after `enter_dual_io()`/`enter_quad_io()` has been issued.
+pub trait MultiIoSpi<E> {
+    /// Transfer `bytes` in place over 2 data lines (SDI mode)
+    fn transfer_dual<'w>(&mut self, bytes: &'w mut [u8]) -> Result<&'w [u8], E>;
+    /// Transfer `bytes` in place over 4 data lines (SQI mode)
+    fn transfer_quad<'w>(&mut self, bytes: &'w mut [u8]) -> Result<&'w [u8], E>;
+}
+
+/// Marker for `MultiIoSpi` transports that actually drive Dual/Quad I/O lines
+///
+/// `enter_dual_io()`/`enter_quad_io()` are bound to this trait rather than plain `MultiIoSpi`,
+/// so they can't be named on a `Sram23x<.., NoMultiIo>` (the default `MIO` produced by
+/// `new()`). Implement this for your multi-lane SPI type alongside `MultiIoSpi` to use it with
+/// `new_multi_io()`.
+pub trait MultiIoTransport<E>: MultiIoSpi<E> {}
+
+/// The default multi-lane transport used by devices that never enter Dual/Quad I/O mode
+///
+/// Deliberately does not implement `MultiIoTransport`, so `enter_dual_io()`/`enter_quad_io()`
+/// are unreachable on a `Sram23x` built with `new()`; only `transfer_dual()`/`transfer_quad()`
+/// exist to satisfy `MultiIoSpi`, and they panic if ever somehow called.
+#[derive(Debug, Default)]
+pub struct NoMultiIo;
+
+impl<E> MultiIoSpi<E> for NoMultiIo {
+    fn transfer_dual<'w>(&mut self, _bytes: &'w mut [u8]) -> Result<&'w [u8], E> {
+        unreachable!("NoMultiIo never enters Dual/Quad I/O mode; use new_multi_io() instead")
+    }
+
+    fn transfer_quad<'w>(&mut self, _bytes: &'w mut [u8]) -> Result<&'w [u8], E> {
+        unreachable!("NoMultiIo never enters Dual/Quad I/O mode; use new_multi_io() instead")
+    }
 }
 
 /// All possible instructions
@@ -36,7 +36,16 @@ impl_device_type!(M23xv512, 3, false, false, 0xFFFF_u32);
 impl_device_type!(M23x1024, 4, true, false, 0x1FFFF_u32);
 impl_device_type!(M23xv1024, 4, false, false, 0x1FFFF_u32);
 
-impl<SPI, S, P, CS, HOLD, DT> Sram23x<SPI, CS, HOLD, DT>
+/// Marker for the battery-backed NVSRAM device types whose contents survive power loss
+#[cfg(feature = "config")]
+pub trait NvSram: DeviceType {}
+
+#[cfg(feature = "config")]
+impl NvSram for device_type::M23xv512 {}
+#[cfg(feature = "config")]
+impl NvSram for device_type::M23xv1024 {}
+
+impl<SPI, S, P, CS, HOLD, DT> Sram23x<SPI, CS, HOLD, DT, NoMultiIo>
 where
     SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
     CS: OutputPin<Error = P>,
@@ -51,13 +60,24 @@ where
             hold,
             dt,
             mode: 0,
+            io_mode: IoMode::Single,
+            mio: None,
         };
         sram.cs.set_high().map_err(Error::PinError)?;
         sram.set_hold(false)?;
         sram.get_mode()?;
         Ok(sram)
     }
+}
 
+impl<SPI, S, P, CS, HOLD, DT, MIO> Sram23x<SPI, CS, HOLD, DT, MIO>
+where
+    SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
+    CS: OutputPin<Error = P>,
+    HOLD: OutputPin<Error = P>,
+    DT: DeviceType,
+    MIO: MultiIoSpi<S>,
+{
     /// Transfer data over the SPI bus
     pub fn transfer(&mut self, bytes: &mut [u8]) -> SpiRes<S, P> {
         self.cs.set_low().map_err(Error::PinError)?;
@@ -66,10 +86,48 @@ where
         Ok(())
     }
 
+    /// Transfer data over the currently active bus width (standard, Dual, or Quad I/O),
+    /// without framing chip-select
+    fn lane_transfer(&mut self, bytes: &mut [u8]) -> SpiRes<S, P> {
+        match self.io_mode {
+            IoMode::Single => {
+                self.spi.transfer(bytes).map_err(Error::SpiError)?;
+            }
+            IoMode::Dual => {
+                let mio = self.mio.as_mut().ok_or(Error::InvalidOperatingMode)?;
+                mio.transfer_dual(bytes).map_err(Error::SpiError)?;
+            }
+            IoMode::Quad => {
+                let mio = self.mio.as_mut().ok_or(Error::InvalidOperatingMode)?;
+                mio.transfer_quad(bytes).map_err(Error::SpiError)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Transfer data over the currently active bus width, framed by chip-select
+    fn io_transfer(&mut self, bytes: &mut [u8]) -> SpiRes<S, P> {
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.lane_transfer(bytes)?;
+        self.cs.set_high().map_err(Error::PinError)?;
+        Ok(())
+    }
+
+    /// Clock out the Reset I/O instruction in the currently active bus width, returning the
+    /// device to standard single-lane SPI
+    pub fn reset_io(&mut self) -> SpiRes<S, P> {
+        self.io_transfer(&mut [Instruction::ResetIo as u8])?;
+        self.io_mode = IoMode::Single;
+        Ok(())
+    }
+
     /// Return the operating mode/status of the device
+    ///
+    /// Clocked over the currently active bus width, so this clocks single-lane before
+    /// `enter_dual_io()`/`enter_quad_io()` and over the active multi-lane width after.
     pub fn get_mode(&mut self) -> Result<u8, Error<S, P>> {
         let mut buf: [u8; 2] = [Instruction::ReadMode as u8, 0];
-        self.transfer(&mut buf)?;
+        self.io_transfer(&mut buf)?;
         self.get_mode_bits(buf[1])?;
         self.mode = buf[1];
         Ok(self.mode)
@@ -84,15 +142,23 @@ where
     }
 
     /// Sets the operating mode/status of the device
+    ///
+    /// Clocked over the currently active bus width, so this clocks single-lane before
+    /// `enter_dual_io()`/`enter_quad_io()` and over the active multi-lane width after.
     pub fn set_mode(&mut self, mode: u8) -> SpiRes<S, P> {
         let mut buf: [u8; 2] = [Instruction::WriteMode as u8, mode];
-        self.transfer(&mut buf)?;
+        self.io_transfer(&mut buf)?;
         self.mode = mode;
         Ok(())
     }
 
     /// Enable the hold pin (bring it low), which prevents data transmission
+    ///
+    /// This is a no-op in Quad I/O mode, since HOLD is repurposed as SIO3 there.
     pub fn set_hold(&mut self, enabled: bool) -> SpiRes<S, P> {
+        if self.io_mode == IoMode::Quad {
+            return Ok(());
+        }
         if DT::HOLD_PIN {
             if enabled {
                 self.enable_hold_feature()?;
@@ -132,7 +198,7 @@ where
             DT::fill_address(&mut addr, Instruction::Read);
             let data = addr.to_be_bytes();
             let mut buf: [u8; 5] = self.get_address_array(data, 0)?;
-            self.transfer(&mut buf[..=DT::ADDRESS_BYTES])?;
+            self.io_transfer(&mut buf[..=DT::ADDRESS_BYTES])?;
             Ok(buf[DT::ADDRESS_BYTES])
         }
     }
@@ -146,7 +212,7 @@ where
             DT::fill_address(&mut addr, Instruction::Write);
             let data = addr.to_be_bytes();
             let mut buf: [u8; 5] = self.get_address_array(data, byte)?;
-            self.transfer(&mut buf[..=DT::ADDRESS_BYTES])?;
+            self.io_transfer(&mut buf[..=DT::ADDRESS_BYTES])?;
             Ok(())
         }
     }
@@ -175,12 +241,12 @@ where
                     buf[0] = data[0];
                     buf[1] = data[2];
                     buf[2] = data[3];
-                    self.transfer(&mut buf[..size])?;
+                    self.io_transfer(&mut buf[..size])?;
                     TryFrom::try_from(&buf[3..35]).unwrap()
                 }
                 4 => {
                     buf[..4].clone_from_slice(&data[..]);
-                    self.transfer(&mut buf[..size])?;
+                    self.io_transfer(&mut buf[..size])?;
                     TryFrom::try_from(&buf[4..]).unwrap()
                 }
                 _ => return Err(Error::InvalidAddressSize),
@@ -214,7 +280,7 @@ where
                 _ => return Err(Error::InvalidAddressSize),
             };
             let size: usize = DT::ADDRESS_BYTES + 32;
-            self.transfer(&mut buf[..size])?;
+            self.io_transfer(&mut buf[..size])?;
             Ok(())
         }
     }
@@ -247,14 +313,198 @@ where
                 _ => return Err(Error::InvalidAddressSize),
             };
             self.cs.set_low().map_err(Error::PinError)?;
-            self.spi
-                .transfer(&mut buf[..DT::ADDRESS_BYTES])
-                .map_err(Error::SpiError)?;
-            self.spi.transfer(&mut bytes[..]).map_err(Error::SpiError)?;
+            self.lane_transfer(&mut buf[..DT::ADDRESS_BYTES])?;
+            self.lane_transfer(&mut bytes[..])?;
             self.cs.set_high().map_err(Error::PinError)?;
             Ok(())
         }
     }
+
+    /// Check that a `len`-byte access starting at `address` stays within the device's memory
+    fn check_range(&self, address: u32, len: usize) -> Result<(), Error<S, P>> {
+        if address as u64 + len as u64 > DT::MAX as u64 + 1 {
+            Err(Error::InvalidAddress)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Switch the device into `OperatingMode::Sequential` if it isn't already there
+    fn ensure_sequential_mode(&mut self) -> SpiRes<S, P> {
+        if self.mode.get_bits(6..8) != (OperatingMode::Sequential as u8).get_bits(6..8) {
+            self.set_mode(OperatingMode::Sequential as u8)?;
+        }
+        Ok(())
+    }
+
+    /// Build the address frame (command byte folded in via `DT::fill_address`) for a
+    /// sequential-style transfer
+    fn address_frame(&self, address: u32, instruction: Instruction) -> Result<[u8; 4], Error<S, P>> {
+        let mut addr = address;
+        DT::fill_address(&mut addr, instruction);
+        let data = addr.to_be_bytes();
+        match DT::ADDRESS_BYTES {
+            3 => Ok([data[0], data[2], data[3], 0]),
+            4 => Ok(data),
+            _ => Err(Error::InvalidAddressSize),
+        }
+    }
+
+    /// Read an arbitrary number of bytes starting from an address, switching the device into
+    /// `OperatingMode::Sequential` first if necessary so the read isn't limited to a single
+    /// 32-byte page
+    pub fn read(&mut self, address: u32, bytes: &mut [u8]) -> SpiRes<S, P> {
+        self.check_range(address, bytes.len())?;
+        self.ensure_sequential_mode()?;
+        self.sequential(address, bytes, Instruction::Read)
+    }
+
+    /// Write an arbitrary number of bytes starting from an address, switching the device into
+    /// `OperatingMode::Sequential` first if necessary so the write isn't limited to a single
+    /// 32-byte page
+    ///
+    /// The data is sent in 32-byte pieces regardless of mode; that's only to satisfy
+    /// `Transfer`'s mutable-buffer requirement via a fixed-size on-stack scratch buffer, not a
+    /// page-boundary concern (sequential mode has none).
+    pub fn write(&mut self, address: u32, bytes: &[u8]) -> SpiRes<S, P> {
+        self.check_range(address, bytes.len())?;
+        self.ensure_sequential_mode()?;
+        let mut addr_buf = self.address_frame(address, Instruction::Write)?;
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.lane_transfer(&mut addr_buf[..DT::ADDRESS_BYTES])?;
+        let mut chunk: [u8; 32] = [0; 32];
+        for piece in bytes.chunks(32) {
+            chunk[..piece.len()].clone_from_slice(piece);
+            self.lane_transfer(&mut chunk[..piece.len()])?;
+        }
+        self.cs.set_high().map_err(Error::PinError)?;
+        Ok(())
+    }
+
+    /// Stream-read `bytes.len()` bytes starting at `address` in `chunk_len`-sized pieces,
+    /// holding chip-select low and emitting the address only once so the HAL's SPI
+    /// implementation can burst each `chunk_len` piece (e.g. via DMA) instead of framing a
+    /// fresh chip-select transaction per call.
+    ///
+    /// The caller must already have the device in `OperatingMode::Sequential` (see
+    /// `set_mode()`); this does not check or change the mode. `address + bytes.len()` must
+    /// stay within `DT::MAX`.
+    pub fn read_stream(&mut self, address: u32, bytes: &mut [u8], chunk_len: usize) -> SpiRes<S, P> {
+        let mut addr_buf = self.address_frame(address, Instruction::Read)?;
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.lane_transfer(&mut addr_buf[..DT::ADDRESS_BYTES])?;
+        for chunk in bytes.chunks_mut(chunk_len.max(1)) {
+            self.lane_transfer(chunk)?;
+        }
+        self.cs.set_high().map_err(Error::PinError)?;
+        Ok(())
+    }
+
+    /// Stream-write the concatenation of `chunks` starting at `address`, holding chip-select
+    /// low and emitting the address only once, then pumping each chunk through the SPI bus
+    /// back-to-back so the HAL's SPI implementation can burst large, caller-sized transfers
+    /// (e.g. via DMA) instead of framing a fresh chip-select transaction per call.
+    ///
+    /// On the single-lane path each chunk is hence passed straight to the HAL with no copy
+    /// and no size cap. Dual/Quad I/O transfers go through `MultiIoSpi`, which needs a
+    /// mutable buffer, so that path copies through a page-sized on-stack scratch buffer and
+    /// is capped at 32 bytes per underlying transfer.
+    ///
+    /// The caller must already have the device in `OperatingMode::Sequential` (see
+    /// `set_mode()`); this does not check or change the mode. The total length streamed
+    /// must stay within `DT::MAX`.
+    pub fn write_stream(
+        &mut self,
+        address: u32,
+        chunks: &mut dyn Iterator<Item = &[u8]>,
+    ) -> SpiRes<S, P> {
+        let mut addr_buf = self.address_frame(address, Instruction::Write)?;
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.lane_transfer(&mut addr_buf[..DT::ADDRESS_BYTES])?;
+        match self.io_mode {
+            IoMode::Single => {
+                for chunk in chunks {
+                    self.spi.write(chunk).map_err(Error::SpiError)?;
+                }
+            }
+            IoMode::Dual | IoMode::Quad => {
+                let mut scratch: [u8; 32] = [0; 32];
+                for chunk in chunks {
+                    for piece in chunk.chunks(scratch.len()) {
+                        scratch[..piece.len()].clone_from_slice(piece);
+                        self.lane_transfer(&mut scratch[..piece.len()])?;
+                    }
+                }
+            }
+        }
+        self.cs.set_high().map_err(Error::PinError)?;
+        Ok(())
+    }
+}
+
+impl<SPI, S, P, CS, HOLD, DT, MIO> Sram23x<SPI, CS, HOLD, DT, MIO>
+where
+    SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
+    CS: OutputPin<Error = P>,
+    HOLD: OutputPin<Error = P>,
+    DT: DeviceType,
+    MIO: MultiIoTransport<S>,
+{
+    /// Initialize the SRAM device with a user-supplied Dual/Quad I/O transport, disable the
+    /// pin's hold feature, and obtain the operating mode
+    ///
+    /// Bound to `MultiIoTransport` rather than plain `MultiIoSpi`, so `mio` is taken here
+    /// directly (no turbofish needed to pick `MIO`) and `enter_dual_io()`/`enter_quad_io()`
+    /// stay reachable only through an instance built this way, never through `new()`'s
+    /// `NoMultiIo`.
+    pub fn new_multi_io(spi: SPI, cs: CS, hold: HOLD, dt: DT, mio: MIO) -> Result<Self, Error<S, P>> {
+        let mut sram = Sram23x {
+            spi,
+            cs,
+            hold,
+            dt,
+            mode: 0,
+            io_mode: IoMode::Single,
+            mio: Some(mio),
+        };
+        sram.cs.set_high().map_err(Error::PinError)?;
+        sram.set_hold(false)?;
+        sram.get_mode()?;
+        Ok(sram)
+    }
+
+    /// Enter Dual I/O (SDI) mode. After this, `read_byte()`/`write_byte()`/`read_page()`/
+    /// `write_page()`/`read_sequential()`/`write_sequential()` clock their command, address,
+    /// and data bytes over 2 lines using the transport supplied to `new_multi_io()` instead of
+    /// the single-lane `spi`
+    pub fn enter_dual_io(&mut self) -> SpiRes<S, P> {
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.spi
+            .write(&[Instruction::EnterDualIo as u8])
+            .map_err(Error::SpiError)?;
+        self.cs.set_high().map_err(Error::PinError)?;
+        self.io_mode = IoMode::Dual;
+        Ok(())
+    }
+
+    /// Enter Quad I/O (SQI) mode. After this, command/address/data bytes are clocked over 4
+    /// lines using the transport supplied to `new_multi_io()` instead of the single-lane `spi`.
+    ///
+    /// HOLD is repurposed as SIO3 in this mode, so the HOLD feature is disabled and the pin
+    /// driven inactive first, and `set_hold()` becomes a no-op until `reset_io()` is called.
+    pub fn enter_quad_io(&mut self) -> SpiRes<S, P> {
+        self.disable_hold_feature()?;
+        if DT::HOLD_PIN {
+            self.hold.set_high().map_err(Error::PinError)?;
+        }
+        self.cs.set_low().map_err(Error::PinError)?;
+        self.spi
+            .write(&[Instruction::EnterQuadIo as u8])
+            .map_err(Error::SpiError)?;
+        self.cs.set_high().map_err(Error::PinError)?;
+        self.io_mode = IoMode::Quad;
+        Ok(())
+    }
 }
 
 // Tests
@@ -0,0 +1,39 @@
+use super::sram23x::DeviceType;
+use super::*;
+use embedded_storage::{ReadStorage, Storage};
+use hal::blocking::spi::{Transfer, Write};
+use hal::digital::v2::OutputPin;
+
+impl<SPI, S, P, CS, HOLD, DT, MIO> ReadStorage for Sram23x<SPI, CS, HOLD, DT, MIO>
+where
+    SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
+    CS: OutputPin<Error = P>,
+    HOLD: OutputPin<Error = P>,
+    DT: DeviceType,
+    MIO: MultiIoSpi<S>,
+{
+    type Error = Error<S, P>;
+
+    /// Read `bytes.len()` bytes starting at `offset`, switching into sequential mode first
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        self.read(offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        DT::MAX as usize + 1
+    }
+}
+
+impl<SPI, S, P, CS, HOLD, DT, MIO> Storage for Sram23x<SPI, CS, HOLD, DT, MIO>
+where
+    SPI: Transfer<u8, Error = S> + Write<u8, Error = S>,
+    CS: OutputPin<Error = P>,
+    HOLD: OutputPin<Error = P>,
+    DT: DeviceType,
+    MIO: MultiIoSpi<S>,
+{
+    /// Write `bytes` starting at `offset`, switching into sequential mode first
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.write(offset, bytes)
+    }
+}